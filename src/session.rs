@@ -0,0 +1,248 @@
+//! Profile and session storage: each profile gets its own sessions file and
+//! its own config overrides, all rooted under the user's XDG config
+//! directory.
+
+use crate::tui::styles::Theme;
+use crate::tui::themes::loader::load_theme_for_profile;
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// Base directory for all profile data: `$XDG_CONFIG_HOME/agent-of-empires`,
+/// falling back to `$HOME/.config/agent-of-empires`.
+fn base_dir() -> Result<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .ok_or_else(|| anyhow!("Neither XDG_CONFIG_HOME nor HOME is set"))?;
+
+    Ok(config_home.join("agent-of-empires"))
+}
+
+fn profile_dir(name: &str) -> Result<PathBuf> {
+    Ok(base_dir()?.join("profiles").join(name))
+}
+
+fn profile_exists(name: &str) -> Result<bool> {
+    Ok(profile_dir(name)?.is_dir())
+}
+
+/// Global config shared across profiles.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    pub default_profile: String,
+    /// Global lightness adjustment applied to the active theme, unless a
+    /// profile sets its own [`ProfileConfig::theme_lightness`].
+    pub theme_lightness: Option<f32>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            default_profile: "default".to_string(),
+            theme_lightness: None,
+        }
+    }
+}
+
+impl Config {
+    fn path() -> Result<PathBuf> {
+        Ok(base_dir()?.join("config.toml"))
+    }
+
+    pub fn load() -> Result<Config> {
+        let path = Self::path()?;
+        let Ok(contents) = fs::read_to_string(&path) else {
+            return Ok(Config::default());
+        };
+        toml::from_str(&contents).map_err(|e| anyhow!("Failed to parse config: {}", e))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = toml::to_string_pretty(self)?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+/// A single saved session within a profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Instance {
+    pub title: String,
+    pub path: String,
+}
+
+impl Instance {
+    pub fn new(title: impl Into<String>, path: impl Into<String>) -> Instance {
+        Instance {
+            title: title.into(),
+            path: path.into(),
+        }
+    }
+}
+
+/// Session storage for a single profile.
+pub struct Storage {
+    sessions_path: PathBuf,
+}
+
+impl Storage {
+    pub fn new(profile: &str) -> Result<Storage> {
+        let dir = profile_dir(profile)?;
+        fs::create_dir_all(&dir)?;
+        Ok(Storage {
+            sessions_path: dir.join("sessions.toml"),
+        })
+    }
+
+    pub fn save(&self, instances: &[Instance]) -> Result<()> {
+        #[derive(Serialize)]
+        struct SessionsFile<'a> {
+            instances: &'a [Instance],
+        }
+        let contents = toml::to_string_pretty(&SessionsFile { instances })?;
+        fs::write(&self.sessions_path, contents)?;
+        Ok(())
+    }
+
+    pub fn load(&self) -> Result<Vec<Instance>> {
+        #[derive(Deserialize, Default)]
+        struct SessionsFile {
+            #[serde(default)]
+            instances: Vec<Instance>,
+        }
+        let Ok(contents) = fs::read_to_string(&self.sessions_path) else {
+            return Ok(Vec::new());
+        };
+        let file: SessionsFile =
+            toml::from_str(&contents).map_err(|e| anyhow!("Failed to parse sessions: {}", e))?;
+        Ok(file.instances)
+    }
+}
+
+pub fn create_profile(name: &str) -> Result<()> {
+    fs::create_dir_all(profile_dir(name)?)?;
+    Ok(())
+}
+
+pub fn delete_profile(name: &str) -> Result<()> {
+    if name == "default" {
+        bail!("Cannot delete the default profile");
+    }
+    let dir = profile_dir(name)?;
+    if dir.is_dir() {
+        fs::remove_dir_all(dir)?;
+    }
+    Ok(())
+}
+
+pub fn list_profiles() -> Result<Vec<String>> {
+    let dir = base_dir()?.join("profiles");
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+
+    let mut profiles = Vec::new();
+    for entry in entries.flatten() {
+        if entry.path().is_dir() {
+            if let Some(name) = entry.file_name().to_str() {
+                profiles.push(name.to_string());
+            }
+        }
+    }
+    Ok(profiles)
+}
+
+pub fn set_default_profile(name: &str) -> Result<()> {
+    if !profile_exists(name)? {
+        bail!("Profile '{}' does not exist", name);
+    }
+    let mut config = Config::load()?;
+    config.default_profile = name.to_string();
+    config.save()
+}
+
+pub fn rename_profile(old: &str, new: &str) -> Result<()> {
+    if new.is_empty() {
+        bail!("New profile name cannot be empty");
+    }
+    if new.contains('/') || new.contains(std::path::MAIN_SEPARATOR) {
+        bail!("Profile name cannot contain path separators");
+    }
+    if !profile_exists(old)? {
+        bail!("Profile '{}' does not exist", old);
+    }
+    if profile_exists(new)? {
+        bail!("Profile '{}' already exists", new);
+    }
+
+    fs::rename(profile_dir(old)?, profile_dir(new)?)?;
+
+    let mut config = Config::load()?;
+    if config.default_profile == old {
+        config.default_profile = new.to_string();
+        config.save()?;
+    }
+    Ok(())
+}
+
+/// Per-profile config overrides, layered on top of the global config.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfileConfig {
+    pub updates: Option<UpdatesConfigOverride>,
+    /// Theme name this profile should use instead of the global default.
+    pub theme: Option<String>,
+    /// Lightness adjustment for this profile's theme, overriding
+    /// [`Config::theme_lightness`] when set.
+    pub theme_lightness: Option<f32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdatesConfigOverride {
+    pub check_enabled: Option<bool>,
+}
+
+fn profile_config_path(name: &str) -> Result<PathBuf> {
+    Ok(profile_dir(name)?.join("config.toml"))
+}
+
+pub fn load_profile_config(name: &str) -> Result<ProfileConfig> {
+    let path = profile_config_path(name)?;
+    let Ok(contents) = fs::read_to_string(&path) else {
+        return Ok(ProfileConfig::default());
+    };
+    toml::from_str(&contents).map_err(|e| anyhow!("Failed to parse profile config: {}", e))
+}
+
+pub fn save_profile_config(name: &str, config: &ProfileConfig) -> Result<()> {
+    let path = profile_config_path(name)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let contents = toml::to_string_pretty(config)?;
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+/// Resolve the theme a profile should render with: its own `theme` override
+/// when set (otherwise `global_default`), with its own `theme_lightness`
+/// override applied on top (otherwise the global config's).
+pub fn active_theme_for_profile(profile: &str, global_default: &str) -> Result<Theme> {
+    let profile_config = load_profile_config(profile)?;
+    let global_config = Config::load()?;
+
+    let theme = load_theme_for_profile(profile_config.theme.as_deref(), global_default);
+    let lightness_delta = profile_config
+        .theme_lightness
+        .or(global_config.theme_lightness);
+
+    Ok(match lightness_delta {
+        Some(delta) => theme.with_lightness(delta),
+        None => theme,
+    })
+}
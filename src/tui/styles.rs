@@ -1,7 +1,8 @@
 //! TUI theme and styling
 
-use super::themes::color::parse_hex_color;
-use ratatui::style::Color;
+use super::themes::capability::{downgrade_color, ColorCapability, ResolvedCapability};
+use super::themes::color::{adjust_lightness, modifier_names, parse_hex_color, parse_modifier};
+use ratatui::style::{Color, Modifier, Style};
 use serde::{Deserialize, Deserializer, Serialize};
 use std::ops::Deref;
 
@@ -55,32 +56,163 @@ impl Serialize for ThemeColor {
     }
 }
 
+/// A styled theme slot: a foreground color plus optional background and
+/// text modifiers (bold, italic, ...).
+///
+/// Deserializes from either a bare hex string (color only, matching the
+/// historical `ThemeColor`-only format) or a table:
+/// `{ fg = "#39ff14", bg = "#101214", modifiers = ["bold", "italic"] }`.
+#[derive(Debug, Clone, Copy)]
+pub struct ThemeStyle {
+    pub fg: ThemeColor,
+    pub bg: Option<ThemeColor>,
+    pub modifiers: Modifier,
+}
+
+impl ThemeStyle {
+    /// Fold this slot into a `ratatui::style::Style`.
+    pub fn to_style(self) -> Style {
+        let mut style = Style::default().fg(*self.fg).add_modifier(self.modifiers);
+        if let Some(bg) = self.bg {
+            style = style.bg(*bg);
+        }
+        style
+    }
+
+    /// Downgrade this slot's colors for a terminal without truecolor
+    /// support, leaving modifiers untouched.
+    fn downgraded(self, capability: ResolvedCapability) -> ThemeStyle {
+        ThemeStyle {
+            fg: ThemeColor(downgrade_color(*self.fg, capability)),
+            bg: self
+                .bg
+                .map(|color| ThemeColor(downgrade_color(*color, capability))),
+            modifiers: self.modifiers,
+        }
+    }
+
+    /// Shift this slot's colors toward lighter (`delta > 0`) or darker
+    /// (`delta < 0`), leaving modifiers untouched.
+    fn with_lightness(self, delta: f32) -> ThemeStyle {
+        ThemeStyle {
+            fg: ThemeColor(adjust_lightness(*self.fg, delta)),
+            bg: self.bg.map(|color| ThemeColor(adjust_lightness(*color, delta))),
+            modifiers: self.modifiers,
+        }
+    }
+}
+
+impl Deref for ThemeStyle {
+    type Target = Color;
+    fn deref(&self) -> &Self::Target {
+        self.fg.as_ref()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ThemeStyleRepr {
+    Bare(String),
+    Table {
+        fg: String,
+        bg: Option<String>,
+        #[serde(default)]
+        modifiers: Vec<String>,
+    },
+}
+
+impl<'de> Deserialize<'de> for ThemeStyle {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match ThemeStyleRepr::deserialize(deserializer)? {
+            ThemeStyleRepr::Bare(hex) => {
+                let fg = parse_hex_color(&hex)
+                    .map(ThemeColor)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(ThemeStyle {
+                    fg,
+                    bg: None,
+                    modifiers: Modifier::empty(),
+                })
+            }
+            ThemeStyleRepr::Table { fg, bg, modifiers } => {
+                let fg = parse_hex_color(&fg)
+                    .map(ThemeColor)
+                    .map_err(serde::de::Error::custom)?;
+                let bg = bg
+                    .map(|hex| parse_hex_color(&hex).map(ThemeColor))
+                    .transpose()
+                    .map_err(serde::de::Error::custom)?;
+                let mut flags = Modifier::empty();
+                for name in modifiers {
+                    flags |= parse_modifier(&name).map_err(serde::de::Error::custom)?;
+                }
+                Ok(ThemeStyle {
+                    fg,
+                    bg,
+                    modifiers: flags,
+                })
+            }
+        }
+    }
+}
+
+impl Serialize for ThemeStyle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if self.bg.is_none() && self.modifiers.is_empty() {
+            return self.fg.serialize(serializer);
+        }
+
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("ThemeStyle", 3)?;
+        state.serialize_field("fg", &self.fg)?;
+        state.serialize_field("bg", &self.bg)?;
+        state.serialize_field("modifiers", &modifier_names(self.modifiers))?;
+        state.end()
+    }
+}
+
+impl From<Color> for ThemeStyle {
+    fn from(color: Color) -> Self {
+        ThemeStyle {
+            fg: ThemeColor(color),
+            bg: None,
+            modifiers: Modifier::empty(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Theme {
     // Background and borders
-    pub background: ThemeColor,
-    pub border: ThemeColor,
-    pub terminal_border: ThemeColor,
-    pub selection: ThemeColor,
-    pub session_selection: ThemeColor,
+    pub background: ThemeStyle,
+    pub border: ThemeStyle,
+    pub terminal_border: ThemeStyle,
+    pub selection: ThemeStyle,
+    pub session_selection: ThemeStyle,
 
     // Text colors
-    pub title: ThemeColor,
-    pub text: ThemeColor,
-    pub dimmed: ThemeColor,
-    pub hint: ThemeColor,
+    pub title: ThemeStyle,
+    pub text: ThemeStyle,
+    pub dimmed: ThemeStyle,
+    pub hint: ThemeStyle,
 
     // Status colors
-    pub running: ThemeColor,
-    pub waiting: ThemeColor,
-    pub idle: ThemeColor,
-    pub error: ThemeColor,
-    pub terminal_active: ThemeColor,
+    pub running: ThemeStyle,
+    pub waiting: ThemeStyle,
+    pub idle: ThemeStyle,
+    pub error: ThemeStyle,
+    pub terminal_active: ThemeStyle,
 
     // UI elements
-    pub group: ThemeColor,
-    pub search: ThemeColor,
-    pub accent: ThemeColor,
+    pub group: ThemeStyle,
+    pub search: ThemeStyle,
+    pub accent: ThemeStyle,
 }
 
 impl Default for Theme {
@@ -92,26 +224,172 @@ impl Default for Theme {
 impl Theme {
     pub fn phosphor() -> Self {
         Self {
-            background: ThemeColor(Color::Rgb(16, 20, 18)),
-            border: ThemeColor(Color::Rgb(45, 70, 55)),
-            terminal_border: ThemeColor(Color::Rgb(70, 130, 180)),
-            selection: ThemeColor(Color::Rgb(30, 50, 40)),
-            session_selection: ThemeColor(Color::Rgb(60, 60, 60)),
-
-            title: ThemeColor(Color::Rgb(57, 255, 20)),
-            text: ThemeColor(Color::Rgb(180, 255, 180)),
-            dimmed: ThemeColor(Color::Rgb(80, 120, 90)),
-            hint: ThemeColor(Color::Rgb(100, 160, 120)),
-
-            running: ThemeColor(Color::Rgb(0, 255, 180)),
-            waiting: ThemeColor(Color::Rgb(255, 180, 60)),
-            idle: ThemeColor(Color::Rgb(60, 100, 70)),
-            error: ThemeColor(Color::Rgb(255, 100, 80)),
-            terminal_active: ThemeColor(Color::Rgb(130, 170, 255)),
-
-            group: ThemeColor(Color::Rgb(100, 220, 160)),
-            search: ThemeColor(Color::Rgb(180, 255, 200)),
-            accent: ThemeColor(Color::Rgb(57, 255, 20)),
+            background: Color::Rgb(16, 20, 18).into(),
+            border: Color::Rgb(45, 70, 55).into(),
+            terminal_border: Color::Rgb(70, 130, 180).into(),
+            selection: Color::Rgb(30, 50, 40).into(),
+            session_selection: Color::Rgb(60, 60, 60).into(),
+
+            title: Color::Rgb(57, 255, 20).into(),
+            text: Color::Rgb(180, 255, 180).into(),
+            dimmed: Color::Rgb(80, 120, 90).into(),
+            hint: Color::Rgb(100, 160, 120).into(),
+
+            running: Color::Rgb(0, 255, 180).into(),
+            waiting: Color::Rgb(255, 180, 60).into(),
+            idle: Color::Rgb(60, 100, 70).into(),
+            error: Color::Rgb(255, 100, 80).into(),
+            terminal_active: Color::Rgb(130, 170, 255).into(),
+
+            group: Color::Rgb(100, 220, 160).into(),
+            search: Color::Rgb(180, 255, 200).into(),
+            accent: Color::Rgb(57, 255, 20).into(),
+        }
+    }
+
+    /// Downgrade every color in this theme for terminals without truecolor
+    /// support. A no-op once `capability` resolves to `TrueColor`.
+    pub fn downgraded(self, capability: ColorCapability) -> Theme {
+        let resolved = capability.resolve();
+        if resolved == ResolvedCapability::TrueColor {
+            return self;
+        }
+
+        Theme {
+            background: self.background.downgraded(resolved),
+            border: self.border.downgraded(resolved),
+            terminal_border: self.terminal_border.downgraded(resolved),
+            selection: self.selection.downgraded(resolved),
+            session_selection: self.session_selection.downgraded(resolved),
+
+            title: self.title.downgraded(resolved),
+            text: self.text.downgraded(resolved),
+            dimmed: self.dimmed.downgraded(resolved),
+            hint: self.hint.downgraded(resolved),
+
+            running: self.running.downgraded(resolved),
+            waiting: self.waiting.downgraded(resolved),
+            idle: self.idle.downgraded(resolved),
+            error: self.error.downgraded(resolved),
+            terminal_active: self.terminal_active.downgraded(resolved),
+
+            group: self.group.downgraded(resolved),
+            search: self.search.downgraded(resolved),
+            accent: self.accent.downgraded(resolved),
+        }
+    }
+
+    /// Nudge every color in this theme's lightness by a signed `delta`
+    /// (clamped to keep each color's lightness within `[0, 1]`). Non-RGB
+    /// colors (e.g. already-downgraded ones) are left unchanged.
+    pub fn with_lightness(self, delta: f32) -> Theme {
+        Theme {
+            background: self.background.with_lightness(delta),
+            border: self.border.with_lightness(delta),
+            terminal_border: self.terminal_border.with_lightness(delta),
+            selection: self.selection.with_lightness(delta),
+            session_selection: self.session_selection.with_lightness(delta),
+
+            title: self.title.with_lightness(delta),
+            text: self.text.with_lightness(delta),
+            dimmed: self.dimmed.with_lightness(delta),
+            hint: self.hint.with_lightness(delta),
+
+            running: self.running.with_lightness(delta),
+            waiting: self.waiting.with_lightness(delta),
+            idle: self.idle.with_lightness(delta),
+            error: self.error.with_lightness(delta),
+            terminal_active: self.terminal_active.with_lightness(delta),
+
+            group: self.group.with_lightness(delta),
+            search: self.search.with_lightness(delta),
+            accent: self.accent.with_lightness(delta),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Wrapper {
+        style: ThemeStyle,
+    }
+
+    #[test]
+    fn test_theme_style_bare_hex_is_color_only() {
+        let wrapper: Wrapper = toml::from_str("style = \"#39ff14\"").unwrap();
+        assert_eq!(*wrapper.style, Color::Rgb(57, 255, 20));
+        assert!(wrapper.style.bg.is_none());
+        assert!(wrapper.style.modifiers.is_empty());
+    }
+
+    #[test]
+    fn test_theme_style_table_with_modifiers() {
+        let wrapper: Wrapper = toml::from_str(
+            "style = { fg = \"#39ff14\", bg = \"#101214\", modifiers = [\"bold\", \"italic\"] }",
+        )
+        .unwrap();
+        assert_eq!(*wrapper.style, Color::Rgb(57, 255, 20));
+        assert_eq!(*wrapper.style.bg.unwrap(), Color::Rgb(16, 18, 20));
+        assert!(wrapper.style.modifiers.contains(Modifier::BOLD));
+        assert!(wrapper.style.modifiers.contains(Modifier::ITALIC));
+    }
+
+    #[test]
+    fn test_theme_style_table_without_modifiers_defaults_empty() {
+        let wrapper: Wrapper = toml::from_str("style = { fg = \"#39ff14\" }").unwrap();
+        assert!(wrapper.style.modifiers.is_empty());
+        assert!(wrapper.style.bg.is_none());
+    }
+
+    #[test]
+    fn test_theme_style_invalid_modifier_errors() {
+        let result: Result<Wrapper, _> =
+            toml::from_str("style = { fg = \"#39ff14\", modifiers = [\"blinking\"] }");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_theme_downgraded_to_ansi16_maps_title_and_background() {
+        let theme = Theme::phosphor().downgraded(ColorCapability::Never);
+        assert!(!matches!(*theme.title, Color::Rgb(..)));
+        assert!(!matches!(*theme.background, Color::Rgb(..)));
+    }
+
+    #[test]
+    fn test_theme_downgraded_always_is_noop() {
+        let original = Theme::phosphor();
+        let downgraded = original.clone().downgraded(ColorCapability::Always);
+        assert_eq!(*downgraded.title, *original.title);
+    }
+
+    #[test]
+    fn test_theme_with_lightness_changes_colors() {
+        let original = Theme::phosphor();
+        let lightened = original.clone().with_lightness(0.2);
+        assert_ne!(*lightened.background, *original.background);
+    }
+
+    #[test]
+    fn test_theme_with_lightness_zero_is_effectively_noop() {
+        let original = Theme::phosphor();
+        let unchanged = original.clone().with_lightness(0.0);
+        assert_eq!(*unchanged.title, *original.title);
+        assert_eq!(*unchanged.background, *original.background);
+    }
+
+    #[test]
+    fn test_theme_style_to_style_applies_fg_bg_and_modifiers() {
+        let style = ThemeStyle {
+            fg: ThemeColor(Color::Rgb(1, 2, 3)),
+            bg: Some(ThemeColor(Color::Rgb(4, 5, 6))),
+            modifiers: Modifier::BOLD,
         }
+        .to_style();
+        assert_eq!(style.fg, Some(Color::Rgb(1, 2, 3)));
+        assert_eq!(style.bg, Some(Color::Rgb(4, 5, 6)));
+        assert!(style.add_modifier.contains(Modifier::BOLD));
     }
 }
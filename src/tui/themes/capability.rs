@@ -0,0 +1,267 @@
+//! Terminal color capability detection and RGB downgrade.
+//!
+//! Many terminals (particularly over SSH, or basic `TERM=xterm`) don't
+//! support 24-bit truecolor. [`ColorCapability`] lets a user force a level
+//! explicitly, or leave it on [`ColorCapability::Auto`] to detect from
+//! `COLORTERM`/`TERM`, and [`downgrade_color`] maps an RGB color down to
+//! the nearest color the resolved capability can render.
+
+use ratatui::style::Color;
+use serde::Deserialize;
+
+/// User-facing color capability setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorCapability {
+    /// Detect from the environment (`COLORTERM`/`TERM`).
+    #[default]
+    Auto,
+    /// Force 24-bit truecolor.
+    Always,
+    /// Force the 16 ANSI colors.
+    Never,
+    /// Force the xterm 256-color palette.
+    Ansi256,
+}
+
+impl ColorCapability {
+    /// Resolve this setting against the environment, collapsing `Auto`
+    /// into a concrete detected level.
+    pub fn resolve(self) -> ResolvedCapability {
+        match self {
+            ColorCapability::Always => ResolvedCapability::TrueColor,
+            ColorCapability::Never => ResolvedCapability::Ansi16,
+            ColorCapability::Ansi256 => ResolvedCapability::Ansi256,
+            ColorCapability::Auto => detect_from_env(),
+        }
+    }
+}
+
+/// The concrete color level a terminal supports, after resolving
+/// [`ColorCapability::Auto`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolvedCapability {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+}
+
+fn detect_from_env() -> ResolvedCapability {
+    if let Ok(colorterm) = std::env::var("COLORTERM") {
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return ResolvedCapability::TrueColor;
+        }
+    }
+
+    match std::env::var("TERM") {
+        Ok(term) if term.contains("256color") => ResolvedCapability::Ansi256,
+        _ => ResolvedCapability::Ansi16,
+    }
+}
+
+/// Downgrade a color to the nearest one the resolved capability supports.
+/// Non-RGB colors and `TrueColor` pass through unchanged.
+pub fn downgrade_color(color: Color, capability: ResolvedCapability) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+
+    match capability {
+        ResolvedCapability::TrueColor => color,
+        ResolvedCapability::Ansi256 => Color::Indexed(nearest_256(r, g, b)),
+        ResolvedCapability::Ansi16 => nearest_ansi16(r, g, b),
+    }
+}
+
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Nearest 6-level cube step (0..=5) for a single channel, with its
+/// reconstructed level value.
+fn nearest_cube_step(value: u8) -> (u8, u8) {
+    let step = ((value as f32 / 255.0) * 5.0).round() as u8;
+    (step, CUBE_LEVELS[step as usize])
+}
+
+/// 6x6x6 color cube candidate: xterm index 16-231.
+fn cube_candidate(r: u8, g: u8, b: u8) -> (u8, u32) {
+    let (r_step, r_level) = nearest_cube_step(r);
+    let (g_step, g_level) = nearest_cube_step(g);
+    let (b_step, b_level) = nearest_cube_step(b);
+
+    let index = 16 + 36 * r_step + 6 * g_step + b_step;
+    let distance = squared_distance((r, g, b), (r_level, g_level, b_level));
+    (index, distance)
+}
+
+/// 24-step grayscale ramp candidate: xterm index 232-255.
+fn grayscale_candidate(r: u8, g: u8, b: u8) -> (u8, u32) {
+    let avg = (r as u32 + g as u32 + b as u32) / 3;
+    let step = (((avg as f32 - 8.0) / 10.0).round().clamp(0.0, 23.0)) as u8;
+    let level = (8 + step as u32 * 10) as u8;
+
+    let index = 232 + step;
+    let distance = squared_distance((r, g, b), (level, level, level));
+    (index, distance)
+}
+
+fn squared_distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let dr = a.0 as i32 - b.0 as i32;
+    let dg = a.1 as i32 - b.1 as i32;
+    let db = a.2 as i32 - b.2 as i32;
+    (dr * dr + dg * dg + db * db) as u32
+}
+
+/// Nearest xterm-256 index, choosing whichever of the color cube or the
+/// grayscale ramp minimizes squared RGB distance.
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    let (cube_index, cube_distance) = cube_candidate(r, g, b);
+    let (gray_index, gray_distance) = grayscale_candidate(r, g, b);
+
+    if gray_distance < cube_distance {
+        gray_index
+    } else {
+        cube_index
+    }
+}
+
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Nearest of the 16 ANSI colors by squared RGB distance.
+fn nearest_ansi16(r: u8, g: u8, b: u8) -> Color {
+    ANSI16
+        .iter()
+        .min_by_key(|(_, rgb)| squared_distance((r, g, b), *rgb))
+        .map(|(color, _)| *color)
+        .expect("ANSI16 is non-empty")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    fn test_truecolor_passes_through() {
+        let color = Color::Rgb(57, 255, 20);
+        assert_eq!(downgrade_color(color, ResolvedCapability::TrueColor), color);
+    }
+
+    #[test]
+    fn test_non_rgb_color_passes_through() {
+        assert_eq!(
+            downgrade_color(Color::Indexed(42), ResolvedCapability::Ansi16),
+            Color::Indexed(42)
+        );
+    }
+
+    #[test]
+    fn test_ansi256_pure_red_maps_to_cube() {
+        // Pure red should land in the color cube, not the grayscale ramp.
+        let downgraded = downgrade_color(Color::Rgb(255, 0, 0), ResolvedCapability::Ansi256);
+        assert_eq!(downgraded, Color::Indexed(16 + 36 * 5));
+    }
+
+    #[test]
+    fn test_ansi256_gray_maps_to_grayscale_ramp() {
+        let downgraded = downgrade_color(Color::Rgb(128, 128, 128), ResolvedCapability::Ansi256);
+        assert!(matches!(downgraded, Color::Indexed(idx) if (232..=255).contains(&idx)));
+    }
+
+    #[test]
+    fn test_ansi256_black_and_white_extremes() {
+        assert_eq!(
+            downgrade_color(Color::Rgb(0, 0, 0), ResolvedCapability::Ansi256),
+            Color::Indexed(16)
+        );
+        assert_eq!(
+            downgrade_color(Color::Rgb(255, 255, 255), ResolvedCapability::Ansi256),
+            Color::Indexed(231)
+        );
+    }
+
+    #[test]
+    fn test_ansi16_maps_to_nearest_named_color() {
+        assert_eq!(
+            downgrade_color(Color::Rgb(250, 10, 10), ResolvedCapability::Ansi16),
+            Color::LightRed
+        );
+        assert_eq!(
+            downgrade_color(Color::Rgb(10, 10, 10), ResolvedCapability::Ansi16),
+            Color::Black
+        );
+    }
+
+    #[test]
+    fn test_capability_always_resolves_to_truecolor() {
+        assert_eq!(ColorCapability::Always.resolve(), ResolvedCapability::TrueColor);
+    }
+
+    #[test]
+    fn test_capability_never_resolves_to_ansi16() {
+        assert_eq!(ColorCapability::Never.resolve(), ResolvedCapability::Ansi16);
+    }
+
+    #[test]
+    fn test_capability_ansi256_resolves_to_ansi256() {
+        assert_eq!(ColorCapability::Ansi256.resolve(), ResolvedCapability::Ansi256);
+    }
+
+    #[test]
+    #[serial]
+    fn test_capability_auto_detects_truecolor_from_colorterm() {
+        std::env::set_var("COLORTERM", "truecolor");
+        assert_eq!(ColorCapability::Auto.resolve(), ResolvedCapability::TrueColor);
+        std::env::remove_var("COLORTERM");
+    }
+
+    #[test]
+    #[serial]
+    fn test_capability_auto_detects_ansi256_from_term() {
+        std::env::remove_var("COLORTERM");
+        std::env::set_var("TERM", "xterm-256color");
+        assert_eq!(ColorCapability::Auto.resolve(), ResolvedCapability::Ansi256);
+        std::env::remove_var("TERM");
+    }
+
+    #[test]
+    #[serial]
+    fn test_capability_auto_falls_back_to_ansi16() {
+        std::env::remove_var("COLORTERM");
+        std::env::set_var("TERM", "xterm");
+        assert_eq!(ColorCapability::Auto.resolve(), ResolvedCapability::Ansi16);
+        std::env::remove_var("TERM");
+    }
+
+    #[derive(Deserialize)]
+    struct Wrapper {
+        color_capability: ColorCapability,
+    }
+
+    #[test]
+    fn test_capability_deserializes_from_config_string() {
+        let wrapper: Wrapper = toml::from_str("color_capability = \"always\"").unwrap();
+        assert_eq!(wrapper.color_capability, ColorCapability::Always);
+
+        let wrapper: Wrapper = toml::from_str("color_capability = \"ansi256\"").unwrap();
+        assert_eq!(wrapper.color_capability, ColorCapability::Ansi256);
+
+        assert!(toml::from_str::<Wrapper>("color_capability = \"bogus\"").is_err());
+    }
+}
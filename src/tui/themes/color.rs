@@ -1,5 +1,5 @@
 use anyhow::{bail, Result};
-use ratatui::style::Color;
+use ratatui::style::{Color, Modifier};
 
 /// Parse a hex color string to ratatui Color.
 ///
@@ -61,6 +61,130 @@ fn hex_pair_to_u8(hex: &str) -> Result<u8> {
     })
 }
 
+/// Parse a style modifier name (e.g. "bold") into a `ratatui::style::Modifier` flag.
+///
+/// Recognized names: `bold`, `dim`, `italic`, `underlined`, `reversed`, `crossed_out`.
+pub fn parse_modifier(s: &str) -> Result<Modifier> {
+    match s {
+        "bold" => Ok(Modifier::BOLD),
+        "dim" => Ok(Modifier::DIM),
+        "italic" => Ok(Modifier::ITALIC),
+        "underlined" => Ok(Modifier::UNDERLINED),
+        "reversed" => Ok(Modifier::REVERSED),
+        "crossed_out" => Ok(Modifier::CROSSED_OUT),
+        other => bail!(
+            "Unknown style modifier '{}' (expected one of: bold, dim, italic, underlined, reversed, crossed_out)",
+            other
+        ),
+    }
+}
+
+/// Inverse of [`parse_modifier`]: the modifier names set on `modifiers`, in a
+/// stable order, for round-tripping through serialization.
+pub fn modifier_names(modifiers: Modifier) -> Vec<&'static str> {
+    let mut names = Vec::new();
+    if modifiers.contains(Modifier::BOLD) {
+        names.push("bold");
+    }
+    if modifiers.contains(Modifier::DIM) {
+        names.push("dim");
+    }
+    if modifiers.contains(Modifier::ITALIC) {
+        names.push("italic");
+    }
+    if modifiers.contains(Modifier::UNDERLINED) {
+        names.push("underlined");
+    }
+    if modifiers.contains(Modifier::REVERSED) {
+        names.push("reversed");
+    }
+    if modifiers.contains(Modifier::CROSSED_OUT) {
+        names.push("crossed_out");
+    }
+    names
+}
+
+/// Convert an RGB color (0-255 per channel) to HSL: hue in `[0, 360)`,
+/// saturation and lightness in `[0, 1]`.
+fn rgb_to_hsl(r: u8, g: u8, b: u8) -> (f32, f32, f32) {
+    let r = r as f32 / 255.0;
+    let g = g as f32 / 255.0;
+    let b = b as f32 / 255.0;
+
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+
+    let chroma = max - min;
+    if chroma.abs() < f32::EPSILON {
+        return (0.0, 0.0, l);
+    }
+
+    let s = if l > 0.5 {
+        chroma / (2.0 - max - min)
+    } else {
+        chroma / (max + min)
+    };
+
+    let h = if max == r {
+        ((g - b) / chroma).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / chroma + 2.0
+    } else {
+        (r - g) / chroma + 4.0
+    };
+
+    (h * 60.0, s, l)
+}
+
+/// Convert HSL (hue in `[0, 360)`, saturation and lightness in `[0, 1]`)
+/// back to RGB.
+fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (u8, u8, u8) {
+    if s.abs() < f32::EPSILON {
+        let gray = (l * 255.0).round() as u8;
+        return (gray, gray, gray);
+    }
+
+    let q = if l < 0.5 { l * (1.0 + s) } else { l + s - l * s };
+    let p = 2.0 * l - q;
+    let h = h / 360.0;
+
+    let r = hue_to_channel(p, q, h + 1.0 / 3.0);
+    let g = hue_to_channel(p, q, h);
+    let b = hue_to_channel(p, q, h - 1.0 / 3.0);
+
+    (
+        (r * 255.0).round() as u8,
+        (g * 255.0).round() as u8,
+        (b * 255.0).round() as u8,
+    )
+}
+
+fn hue_to_channel(p: f32, q: f32, t: f32) -> f32 {
+    let t = t.rem_euclid(1.0);
+    if t < 1.0 / 6.0 {
+        p + (q - p) * 6.0 * t
+    } else if t < 1.0 / 2.0 {
+        q
+    } else if t < 2.0 / 3.0 {
+        p + (q - p) * (2.0 / 3.0 - t) * 6.0
+    } else {
+        p
+    }
+}
+
+/// Shift a color's lightness by `delta` (the result is clamped back into
+/// `[0, 1]`). Non-RGB colors pass through unchanged.
+pub fn adjust_lightness(color: Color, delta: f32) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+
+    let (h, s, l) = rgb_to_hsl(r, g, b);
+    let (r, g, b) = hsl_to_rgb(h, s, (l + delta).clamp(0.0, 1.0));
+    Color::Rgb(r, g, b)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,4 +253,79 @@ mod tests {
         let result = parse_hex_color("");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_parse_modifier_valid() {
+        assert_eq!(parse_modifier("bold").unwrap(), Modifier::BOLD);
+        assert_eq!(parse_modifier("dim").unwrap(), Modifier::DIM);
+        assert_eq!(parse_modifier("italic").unwrap(), Modifier::ITALIC);
+        assert_eq!(parse_modifier("underlined").unwrap(), Modifier::UNDERLINED);
+        assert_eq!(parse_modifier("reversed").unwrap(), Modifier::REVERSED);
+        assert_eq!(
+            parse_modifier("crossed_out").unwrap(),
+            Modifier::CROSSED_OUT
+        );
+    }
+
+    #[test]
+    fn test_parse_modifier_invalid() {
+        let err = parse_modifier("blinking").unwrap_err();
+        assert!(err.to_string().contains("Unknown style modifier"));
+    }
+
+    #[test]
+    fn test_modifier_names_round_trip() {
+        let modifiers = Modifier::BOLD | Modifier::ITALIC;
+        assert_eq!(modifier_names(modifiers), vec!["bold", "italic"]);
+    }
+
+    #[test]
+    fn test_rgb_to_hsl_primaries() {
+        assert_eq!(rgb_to_hsl(255, 0, 0), (0.0, 1.0, 0.5));
+        let (h, s, l) = rgb_to_hsl(0, 255, 0);
+        assert!((h - 120.0).abs() < 0.01);
+        assert_eq!((s, l), (1.0, 0.5));
+        let (h, s, l) = rgb_to_hsl(0, 0, 255);
+        assert!((h - 240.0).abs() < 0.01);
+        assert_eq!((s, l), (1.0, 0.5));
+    }
+
+    #[test]
+    fn test_rgb_to_hsl_grayscale_has_no_saturation() {
+        let (_, s, l) = rgb_to_hsl(128, 128, 128);
+        assert_eq!(s, 0.0);
+        assert!((l - 128.0 / 255.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_hsl_round_trips_through_rgb() {
+        for (r, g, b) in [(57u8, 255u8, 20u8), (122, 162, 247), (239, 241, 245)] {
+            let (h, s, l) = rgb_to_hsl(r, g, b);
+            let (r2, g2, b2) = hsl_to_rgb(h, s, l);
+            assert!((r as i16 - r2 as i16).abs() <= 1, "r: {} vs {}", r, r2);
+            assert!((g as i16 - g2 as i16).abs() <= 1, "g: {} vs {}", g, g2);
+            assert!((b as i16 - b2 as i16).abs() <= 1, "b: {} vs {}", b, b2);
+        }
+    }
+
+    #[test]
+    fn test_adjust_lightness_brightens_and_darkens() {
+        let Color::Rgb(r, g, b) = adjust_lightness(Color::Rgb(57, 255, 20), 0.2) else {
+            panic!("expected Rgb");
+        };
+        // Lightness went up, so every channel should be at least as bright.
+        assert!(r >= 57 && g == 255 && b >= 20);
+
+        let darker = adjust_lightness(Color::Rgb(16, 20, 18), -0.1);
+        // Lightness was already low (~0.07), so a -0.1 shift clamps to black.
+        assert_eq!(darker, Color::Rgb(0, 0, 0));
+    }
+
+    #[test]
+    fn test_adjust_lightness_skips_non_rgb() {
+        assert_eq!(
+            adjust_lightness(Color::Indexed(42), 0.3),
+            Color::Indexed(42)
+        );
+    }
 }
@@ -1,39 +1,428 @@
-use crate::tui::styles::Theme;
+use crate::tui::styles::{Theme, ThemeColor, ThemeStyle};
+use crate::tui::themes::capability::ColorCapability;
+use crate::tui::themes::color::{parse_hex_color, parse_modifier};
+use anyhow::{anyhow, bail, Result};
+use ratatui::style::Modifier;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
 use tracing::warn;
 
-pub const AVAILABLE_THEMES: &[&str] = &["phosphor", "tokyo-night", "catppuccin-latte"];
+const BUILTIN_THEME_NAMES: &[&str] = &["phosphor", "tokyo-night", "catppuccin-latte"];
 
 const PHOSPHOR_TOML: &str = include_str!("phosphor.toml");
 const TOKYO_NIGHT_TOML: &str = include_str!("tokyo-night.toml");
 const CATPPUCCIN_LATTE_TOML: &str = include_str!("catppuccin-latte.toml");
 
-pub fn load_theme(name: &str) -> Theme {
-    let toml_str = match name {
-        "phosphor" => PHOSPHOR_TOML,
-        "tokyo-night" => TOKYO_NIGHT_TOML,
-        "catppuccin-latte" => CATPPUCCIN_LATTE_TOML,
-        _ => {
-            warn!("Unknown theme '{}', falling back to phosphor", name);
-            PHOSPHOR_TOML
+/// On-disk representation of a single theme slot: either a bare
+/// `#rrggbb`/palette-name string (color only) or a table with an optional
+/// background and modifiers.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum RawThemeStyle {
+    Color(String),
+    Styled {
+        fg: String,
+        bg: Option<String>,
+        #[serde(default)]
+        modifiers: Vec<String>,
+    },
+}
+
+/// On-disk representation of a theme file. Every slot field is a raw,
+/// unresolved [`RawThemeStyle`] so it can reference a `[palette]` name;
+/// fields are optional so a theme can `extends` another and only override
+/// a handful of them.
+#[derive(Debug, Clone, Deserialize, Default)]
+struct ThemeFile {
+    extends: Option<String>,
+    #[serde(default)]
+    palette: HashMap<String, String>,
+
+    background: Option<RawThemeStyle>,
+    border: Option<RawThemeStyle>,
+    terminal_border: Option<RawThemeStyle>,
+    selection: Option<RawThemeStyle>,
+    session_selection: Option<RawThemeStyle>,
+
+    title: Option<RawThemeStyle>,
+    text: Option<RawThemeStyle>,
+    dimmed: Option<RawThemeStyle>,
+    hint: Option<RawThemeStyle>,
+
+    running: Option<RawThemeStyle>,
+    waiting: Option<RawThemeStyle>,
+    idle: Option<RawThemeStyle>,
+    error: Option<RawThemeStyle>,
+    terminal_active: Option<RawThemeStyle>,
+
+    group: Option<RawThemeStyle>,
+    search: Option<RawThemeStyle>,
+    accent: Option<RawThemeStyle>,
+}
+
+impl ThemeFile {
+    /// Resolve every field's raw hex-or-palette-name representation into a
+    /// `ThemeStyle`, looking up bare names against `self.palette`.
+    fn resolve_colors(self, theme_name: &str) -> Result<ThemeOverrides> {
+        let palette = &self.palette;
+        let field = |field_name: &str, value: Option<RawThemeStyle>| -> Result<Option<ThemeStyle>> {
+            value
+                .map(|raw| resolve_style(theme_name, field_name, raw, palette))
+                .transpose()
+        };
+
+        Ok(ThemeOverrides {
+            background: field("background", self.background)?,
+            border: field("border", self.border)?,
+            terminal_border: field("terminal_border", self.terminal_border)?,
+            selection: field("selection", self.selection)?,
+            session_selection: field("session_selection", self.session_selection)?,
+
+            title: field("title", self.title)?,
+            text: field("text", self.text)?,
+            dimmed: field("dimmed", self.dimmed)?,
+            hint: field("hint", self.hint)?,
+
+            running: field("running", self.running)?,
+            waiting: field("waiting", self.waiting)?,
+            idle: field("idle", self.idle)?,
+            error: field("error", self.error)?,
+            terminal_active: field("terminal_active", self.terminal_active)?,
+
+            group: field("group", self.group)?,
+            search: field("search", self.search)?,
+            accent: field("accent", self.accent)?,
+        })
+    }
+}
+
+/// Resolve a single color reference: a `#rrggbb` literal is parsed
+/// directly, anything else is looked up in the theme's `[palette]` table.
+fn resolve_color(
+    theme_name: &str,
+    field: &str,
+    raw: &str,
+    palette: &HashMap<String, String>,
+) -> Result<ThemeColor> {
+    if raw.starts_with('#') {
+        return parse_hex_color(raw)
+            .map(ThemeColor)
+            .map_err(|e| anyhow!("Theme '{}' field '{}': {}", theme_name, field, e));
+    }
+
+    let hex = palette.get(raw).ok_or_else(|| {
+        anyhow!(
+            "Theme '{}' field '{}' references unknown palette color '{}'",
+            theme_name,
+            field,
+            raw
+        )
+    })?;
+    parse_hex_color(hex)
+        .map(ThemeColor)
+        .map_err(|e| anyhow!("Theme '{}' palette color '{}': {}", theme_name, raw, e))
+}
+
+/// Resolve a single field's raw style into a `ThemeStyle`, resolving its
+/// foreground/background against the theme's `[palette]` table.
+fn resolve_style(
+    theme_name: &str,
+    field: &str,
+    raw: RawThemeStyle,
+    palette: &HashMap<String, String>,
+) -> Result<ThemeStyle> {
+    match raw {
+        RawThemeStyle::Color(hex) => Ok(ThemeStyle {
+            fg: resolve_color(theme_name, field, &hex, palette)?,
+            bg: None,
+            modifiers: Modifier::empty(),
+        }),
+        RawThemeStyle::Styled { fg, bg, modifiers } => {
+            let fg = resolve_color(theme_name, field, &fg, palette)?;
+            let bg = bg
+                .map(|hex| resolve_color(theme_name, field, &hex, palette))
+                .transpose()?;
+            let mut flags = Modifier::empty();
+            for name in modifiers {
+                flags |= parse_modifier(&name)
+                    .map_err(|e| anyhow!("Theme '{}' field '{}': {}", theme_name, field, e))?;
+            }
+            Ok(ThemeStyle {
+                fg,
+                bg,
+                modifiers: flags,
+            })
+        }
+    }
+}
+
+/// Resolved overrides from a theme file, with every field's palette/hex
+/// reference already resolved to a concrete `ThemeStyle`.
+#[derive(Debug, Clone, Default)]
+struct ThemeOverrides {
+    background: Option<ThemeStyle>,
+    border: Option<ThemeStyle>,
+    terminal_border: Option<ThemeStyle>,
+    selection: Option<ThemeStyle>,
+    session_selection: Option<ThemeStyle>,
+
+    title: Option<ThemeStyle>,
+    text: Option<ThemeStyle>,
+    dimmed: Option<ThemeStyle>,
+    hint: Option<ThemeStyle>,
+
+    running: Option<ThemeStyle>,
+    waiting: Option<ThemeStyle>,
+    idle: Option<ThemeStyle>,
+    error: Option<ThemeStyle>,
+    terminal_active: Option<ThemeStyle>,
+
+    group: Option<ThemeStyle>,
+    search: Option<ThemeStyle>,
+    accent: Option<ThemeStyle>,
+}
+
+impl ThemeOverrides {
+    /// Build a fully-resolved `Theme` from this file alone, failing if any
+    /// field was left unset (only valid when the file does not `extends`).
+    fn into_theme(self, name: &str) -> Result<Theme> {
+        let missing = |field: &str| -> anyhow::Error {
+            anyhow!(
+                "Theme '{}' is missing field '{}' and does not extend another theme",
+                name,
+                field
+            )
+        };
+
+        Ok(Theme {
+            background: self.background.ok_or_else(|| missing("background"))?,
+            border: self.border.ok_or_else(|| missing("border"))?,
+            terminal_border: self
+                .terminal_border
+                .ok_or_else(|| missing("terminal_border"))?,
+            selection: self.selection.ok_or_else(|| missing("selection"))?,
+            session_selection: self
+                .session_selection
+                .ok_or_else(|| missing("session_selection"))?,
+
+            title: self.title.ok_or_else(|| missing("title"))?,
+            text: self.text.ok_or_else(|| missing("text"))?,
+            dimmed: self.dimmed.ok_or_else(|| missing("dimmed"))?,
+            hint: self.hint.ok_or_else(|| missing("hint"))?,
+
+            running: self.running.ok_or_else(|| missing("running"))?,
+            waiting: self.waiting.ok_or_else(|| missing("waiting"))?,
+            idle: self.idle.ok_or_else(|| missing("idle"))?,
+            error: self.error.ok_or_else(|| missing("error"))?,
+            terminal_active: self
+                .terminal_active
+                .ok_or_else(|| missing("terminal_active"))?,
+
+            group: self.group.ok_or_else(|| missing("group"))?,
+            search: self.search.ok_or_else(|| missing("search"))?,
+            accent: self.accent.ok_or_else(|| missing("accent"))?,
+        })
+    }
+
+    /// Overlay the fields set in this file on top of a base theme, leaving
+    /// anything unset untouched.
+    fn merge_onto(self, base: Theme) -> Theme {
+        Theme {
+            background: self.background.unwrap_or(base.background),
+            border: self.border.unwrap_or(base.border),
+            terminal_border: self.terminal_border.unwrap_or(base.terminal_border),
+            selection: self.selection.unwrap_or(base.selection),
+            session_selection: self.session_selection.unwrap_or(base.session_selection),
+
+            title: self.title.unwrap_or(base.title),
+            text: self.text.unwrap_or(base.text),
+            dimmed: self.dimmed.unwrap_or(base.dimmed),
+            hint: self.hint.unwrap_or(base.hint),
+
+            running: self.running.unwrap_or(base.running),
+            waiting: self.waiting.unwrap_or(base.waiting),
+            idle: self.idle.unwrap_or(base.idle),
+            error: self.error.unwrap_or(base.error),
+            terminal_active: self.terminal_active.unwrap_or(base.terminal_active),
+
+            group: self.group.unwrap_or(base.group),
+            search: self.search.unwrap_or(base.search),
+            accent: self.accent.unwrap_or(base.accent),
+        }
+    }
+}
+
+/// Directory where users can drop their own theme `.toml` files.
+///
+/// Checks `$XDG_CONFIG_HOME/agent-of-empires/themes` first, falling back to
+/// `$HOME/.config/agent-of-empires/themes` when the former isn't set.
+fn user_themes_dir() -> Option<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(config_home.join("agent-of-empires").join("themes"))
+}
+
+/// Scan the user themes directory for `*.toml` files, returning each
+/// theme's filename stem paired with its raw contents.
+fn discover_user_themes() -> Vec<(String, String)> {
+    let Some(dir) = user_themes_dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut themes = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("toml") {
+            continue;
+        }
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => themes.push((stem.to_string(), contents)),
+            Err(e) => warn!("Failed to read theme file '{}': {}", path.display(), e),
         }
+    }
+    themes.sort_by(|a, b| a.0.cmp(&b.0));
+    themes
+}
+
+/// Warn when a theme file's internal `name` field disagrees with its
+/// filename stem. The file is still loaded either way.
+fn check_declared_name(contents: &str, stem: &str) {
+    let Ok(value) = toml::from_str::<toml::Value>(contents) else {
+        return;
     };
+    if let Some(declared) = value.get("name").and_then(|v| v.as_str()) {
+        if declared != stem {
+            warn!(
+                "Theme file '{}.toml' declares name '{}', which does not match its filename",
+                stem, declared
+            );
+        }
+    }
+}
 
-    match toml::from_str(toml_str) {
+fn builtin_toml(name: &str) -> Option<&'static str> {
+    match name {
+        "phosphor" => Some(PHOSPHOR_TOML),
+        "tokyo-night" => Some(TOKYO_NIGHT_TOML),
+        "catppuccin-latte" => Some(CATPPUCCIN_LATTE_TOML),
+        _ => None,
+    }
+}
+
+/// Raw contents for a theme name, preferring a user theme file over a
+/// built-in one.
+fn raw_theme_contents(name: &str) -> Option<String> {
+    if let Some(contents) = discover_user_themes()
+        .into_iter()
+        .find(|(stem, _)| stem == name)
+        .map(|(_, contents)| contents)
+    {
+        check_declared_name(&contents, name);
+        return Some(contents);
+    }
+    builtin_toml(name).map(|s| s.to_string())
+}
+
+/// Names of every theme available to [`load_theme`]: the built-in set plus
+/// any user themes discovered under the XDG themes directory.
+pub fn available_themes() -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN_THEME_NAMES.iter().map(|s| s.to_string()).collect();
+    for (name, _) in discover_user_themes() {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Resolve a theme by name, following its `extends` chain (if any) and
+/// guarding against cycles.
+fn resolve_theme(name: &str, visited: &mut HashSet<String>) -> Result<Theme> {
+    if !visited.insert(name.to_string()) {
+        bail!(
+            "Theme inheritance cycle detected while resolving '{}' (chain: {:?})",
+            name,
+            visited
+        );
+    }
+
+    let contents =
+        raw_theme_contents(name).ok_or_else(|| anyhow!("Unknown theme '{}'", name))?;
+    let file: ThemeFile =
+        toml::from_str(&contents).map_err(|e| anyhow!("Failed to parse theme '{}': {}", name, e))?;
+    let extends = file.extends.clone();
+    let overrides = file.resolve_colors(name)?;
+
+    match extends {
+        Some(base_name) => {
+            let base = resolve_theme(&base_name, visited)?;
+            Ok(overrides.merge_onto(base))
+        }
+        None => overrides.into_theme(name),
+    }
+}
+
+pub fn load_theme(name: &str) -> Theme {
+    let mut visited = HashSet::new();
+    match resolve_theme(name, &mut visited) {
         Ok(theme) => theme,
         Err(e) => {
-            warn!(
-                "Failed to parse theme '{}': {}, using default phosphor",
-                name, e
-            );
+            warn!("{}, falling back to phosphor", e);
             Theme::phosphor()
         }
     }
 }
 
+/// Resolve the effective theme for a profile: the profile's configured
+/// theme wins when set, otherwise `global_default` is used.
+pub fn load_theme_for_profile(profile_theme: Option<&str>, global_default: &str) -> Theme {
+    load_theme(profile_theme.unwrap_or(global_default))
+}
+
+/// Load a theme and downgrade it to the color depth that `capability`
+/// resolves to (`ColorCapability::Auto` detects it from `COLORTERM`/`TERM`).
+pub fn load_theme_for_terminal(name: &str, capability: ColorCapability) -> Theme {
+    load_theme(name).downgraded(capability)
+}
+
+/// Load a theme and apply an optional lightness adjustment, e.g. from a
+/// `theme_lightness` config knob.
+pub fn load_theme_adjusted(name: &str, lightness_delta: Option<f32>) -> Theme {
+    let theme = load_theme(name);
+    match lightness_delta {
+        Some(delta) => theme.with_lightness(delta),
+        None => theme,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use ratatui::style::Color;
+    use serial_test::serial;
+
+    fn setup_temp_config() -> tempfile::TempDir {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::env::set_var("XDG_CONFIG_HOME", temp.path());
+        temp
+    }
+
+    fn themes_dir_in(temp: &tempfile::TempDir) -> PathBuf {
+        let dir = temp.path().join("agent-of-empires").join("themes");
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
 
     #[test]
     fn test_load_phosphor() {
@@ -64,10 +453,198 @@ mod tests {
     }
 
     #[test]
-    fn test_available_themes_count() {
-        assert_eq!(AVAILABLE_THEMES.len(), 3);
-        assert!(AVAILABLE_THEMES.contains(&"phosphor"));
-        assert!(AVAILABLE_THEMES.contains(&"tokyo-night"));
-        assert!(AVAILABLE_THEMES.contains(&"catppuccin-latte"));
+    fn test_available_themes_includes_builtins() {
+        // Not #[serial]: sibling tests mutate XDG_CONFIG_HOME and may add extra
+        // user themes while this runs, so only assert the builtins are present.
+        let names = available_themes();
+        assert!(names.len() >= 3);
+        assert!(names.contains(&"phosphor".to_string()));
+        assert!(names.contains(&"tokyo-night".to_string()));
+        assert!(names.contains(&"catppuccin-latte".to_string()));
+    }
+
+    #[test]
+    #[serial]
+    fn test_user_theme_is_discovered_and_loaded() {
+        let temp = setup_temp_config();
+        let themes_dir = themes_dir_in(&temp);
+        fs::write(
+            themes_dir.join("my-theme.toml"),
+            include_str!("phosphor.toml"),
+        )
+        .unwrap();
+
+        let names = available_themes();
+        assert!(names.contains(&"my-theme".to_string()));
+
+        let theme = load_theme("my-theme");
+        assert_eq!(*theme.title, Color::Rgb(57, 255, 20));
+
+        std::env::remove_var("XDG_CONFIG_HOME");
+    }
+
+    #[test]
+    #[serial]
+    fn test_user_theme_with_mismatched_name_still_loads() {
+        let temp = setup_temp_config();
+        let themes_dir = themes_dir_in(&temp);
+        let contents = format!(
+            "name = \"totally-different\"\n{}",
+            include_str!("phosphor.toml")
+        );
+        fs::write(themes_dir.join("renamed.toml"), contents).unwrap();
+
+        let theme = load_theme("renamed");
+        assert_eq!(*theme.title, Color::Rgb(57, 255, 20));
+
+        std::env::remove_var("XDG_CONFIG_HOME");
+    }
+
+    #[test]
+    #[serial]
+    fn test_theme_extends_overlays_base() {
+        let temp = setup_temp_config();
+        let themes_dir = themes_dir_in(&temp);
+        fs::write(
+            themes_dir.join("neon-phosphor.toml"),
+            "extends = \"phosphor\"\naccent = \"#ff00ff\"\n",
+        )
+        .unwrap();
+
+        let theme = load_theme("neon-phosphor");
+        assert_eq!(*theme.accent, Color::Rgb(255, 0, 255));
+        // Everything else falls back to the phosphor base.
+        assert_eq!(*theme.background, Color::Rgb(16, 20, 18));
+        assert_eq!(*theme.title, Color::Rgb(57, 255, 20));
+
+        std::env::remove_var("XDG_CONFIG_HOME");
+    }
+
+    #[test]
+    #[serial]
+    fn test_theme_extends_cycle_falls_back_to_phosphor() {
+        let temp = setup_temp_config();
+        let themes_dir = themes_dir_in(&temp);
+        fs::write(
+            themes_dir.join("a.toml"),
+            "extends = \"b\"\naccent = \"#ff0000\"\n",
+        )
+        .unwrap();
+        fs::write(
+            themes_dir.join("b.toml"),
+            "extends = \"a\"\naccent = \"#00ff00\"\n",
+        )
+        .unwrap();
+
+        let theme = load_theme("a");
+        // The cycle is unresolvable, so load_theme degrades to the default.
+        assert_eq!(*theme.title, Color::Rgb(57, 255, 20));
+
+        std::env::remove_var("XDG_CONFIG_HOME");
+    }
+
+    #[test]
+    fn test_theme_missing_field_without_extends_errors() {
+        // A bespoke file that neither extends another theme nor supplies
+        // every required field cannot be resolved on its own.
+        let overrides = ThemeOverrides {
+            accent: Some(ThemeStyle {
+                fg: ThemeColor(Color::Rgb(1, 2, 3)),
+                bg: None,
+                modifiers: Modifier::empty(),
+            }),
+            ..Default::default()
+        };
+        let err = overrides.into_theme("incomplete").unwrap_err();
+        assert!(err.to_string().contains("missing field"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_theme_palette_reference_is_resolved() {
+        let temp = setup_temp_config();
+        let themes_dir = themes_dir_in(&temp);
+        fs::write(
+            themes_dir.join("branded.toml"),
+            "extends = \"phosphor\"\naccent = \"neon\"\n\n[palette]\nneon = \"#ff00ff\"\n",
+        )
+        .unwrap();
+
+        let theme = load_theme("branded");
+        assert_eq!(*theme.accent, Color::Rgb(255, 0, 255));
+
+        std::env::remove_var("XDG_CONFIG_HOME");
+    }
+
+    #[test]
+    #[serial]
+    fn test_theme_unknown_palette_reference_falls_back() {
+        let temp = setup_temp_config();
+        let themes_dir = themes_dir_in(&temp);
+        fs::write(
+            themes_dir.join("broken.toml"),
+            "extends = \"phosphor\"\naccent = \"does-not-exist\"\n",
+        )
+        .unwrap();
+
+        let theme = load_theme("broken");
+        // Resolution fails, so load_theme degrades to the default.
+        assert_eq!(*theme.title, Color::Rgb(57, 255, 20));
+
+        std::env::remove_var("XDG_CONFIG_HOME");
+    }
+
+    #[test]
+    #[serial]
+    fn test_theme_field_table_sets_modifiers_and_bg() {
+        let temp = setup_temp_config();
+        let themes_dir = themes_dir_in(&temp);
+        fs::write(
+            themes_dir.join("bold-title.toml"),
+            "extends = \"phosphor\"\ntitle = { fg = \"#ffffff\", bg = \"#000000\", modifiers = [\"bold\", \"italic\"] }\n",
+        )
+        .unwrap();
+
+        let theme = load_theme("bold-title");
+        assert_eq!(*theme.title, Color::Rgb(255, 255, 255));
+        assert_eq!(theme.title.bg.unwrap().0, Color::Rgb(0, 0, 0));
+        assert!(theme.title.modifiers.contains(Modifier::BOLD));
+        assert!(theme.title.modifiers.contains(Modifier::ITALIC));
+    }
+
+    #[test]
+    fn test_load_theme_for_profile_prefers_profile_override() {
+        let theme = load_theme_for_profile(Some("tokyo-night"), "phosphor");
+        assert_eq!(*theme.title, Color::Rgb(122, 162, 247));
+    }
+
+    #[test]
+    fn test_load_theme_for_profile_falls_back_to_global_default() {
+        let theme = load_theme_for_profile(None, "catppuccin-latte");
+        assert_eq!(*theme.title, Color::Rgb(30, 102, 245));
+    }
+
+    #[test]
+    fn test_load_theme_adjusted_without_delta_is_unchanged() {
+        let theme = load_theme_adjusted("phosphor", None);
+        assert_eq!(*theme.title, Color::Rgb(57, 255, 20));
+    }
+
+    #[test]
+    fn test_load_theme_adjusted_applies_lightness_delta() {
+        let theme = load_theme_adjusted("phosphor", Some(0.2));
+        assert_ne!(*theme.title, Color::Rgb(57, 255, 20));
+    }
+
+    #[test]
+    fn test_load_theme_for_terminal_always_keeps_truecolor() {
+        let theme = load_theme_for_terminal("phosphor", ColorCapability::Always);
+        assert_eq!(*theme.title, Color::Rgb(57, 255, 20));
+    }
+
+    #[test]
+    fn test_load_theme_for_terminal_never_downgrades_to_ansi16() {
+        let theme = load_theme_for_terminal("phosphor", ColorCapability::Never);
+        assert!(!matches!(*theme.title, Color::Rgb(..)));
     }
 }
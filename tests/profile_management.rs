@@ -241,7 +241,6 @@ fn test_profile_config_isolation() -> Result<()> {
     let custom_config = ProfileConfig {
         updates: Some(UpdatesConfigOverride {
             check_enabled: Some(false),
-            ..Default::default()
         }),
         ..Default::default()
     };
@@ -264,3 +263,72 @@ fn test_profile_config_isolation() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+#[serial]
+fn test_profile_theme_isolation() -> Result<()> {
+    use agent_of_empires::session::{
+        active_theme_for_profile, load_profile_config, save_profile_config, ProfileConfig,
+    };
+    use ratatui::style::Color;
+
+    let _temp = setup_temp_home();
+
+    create_profile("work")?;
+    create_profile("default")?;
+
+    // Profile "work" picks tokyo-night, "default" stays unset.
+    let work_config = ProfileConfig {
+        theme: Some("tokyo-night".to_string()),
+        ..Default::default()
+    };
+    save_profile_config("work", &work_config)?;
+
+    let default_config = load_profile_config("default")?;
+    assert!(
+        default_config.theme.is_none(),
+        "Default profile should have no theme override"
+    );
+
+    // The global default is "phosphor"; "work" overrides it, "default" doesn't.
+    let work_theme = active_theme_for_profile("work", "phosphor")?;
+    assert_eq!(*work_theme.title, Color::Rgb(122, 162, 247));
+
+    let default_theme = active_theme_for_profile("default", "phosphor")?;
+    assert_eq!(*default_theme.title, Color::Rgb(57, 255, 20));
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_profile_theme_lightness_override() -> Result<()> {
+    use agent_of_empires::session::{active_theme_for_profile, save_profile_config, Config, ProfileConfig};
+    use ratatui::style::Color;
+
+    let _temp = setup_temp_home();
+
+    create_profile("dim")?;
+    create_profile("bright")?;
+
+    // Global config dims every theme by default...
+    let mut global_config = Config::load()?;
+    global_config.theme_lightness = Some(-0.2);
+    global_config.save()?;
+
+    // ...but "bright" overrides that with its own, brighter adjustment.
+    let bright_config = ProfileConfig {
+        theme_lightness: Some(0.2),
+        ..Default::default()
+    };
+    save_profile_config("bright", &bright_config)?;
+
+    let dim_theme = active_theme_for_profile("dim", "phosphor")?;
+    assert_ne!(*dim_theme.title, Color::Rgb(57, 255, 20));
+
+    let bright_theme = active_theme_for_profile("bright", "phosphor")?;
+    assert_ne!(*bright_theme.title, Color::Rgb(57, 255, 20));
+    assert_ne!(*bright_theme.title, *dim_theme.title);
+
+    Ok(())
+}